@@ -27,6 +27,31 @@ pub struct FunctionalGraph {
     /// `noncycle_lens[v]`: 頂点 `v` から辺を辿り続けたときの非循環節内の頂点数。
     noncycle_lens: Vec<usize>,
 
+    /// ダブリング用テーブル。`up[0] == succs`、`up[j][v]` は `v` から `2^j` 回辺を辿ったときの頂点。
+    ///
+    /// `usize` で表せる最大の `k` を一度に処理できるよう、レベル数は `usize::BITS` だけ用意する。
+    up: Vec<Vec<usize>>,
+
+    /// 逆隣接リスト (CSR 形式) のオフセット。`pred_offsets[v]..pred_offsets[v + 1]` が
+    /// `pred_list` における頂点 `v` の逆辺の範囲となる。長さは `n + 1`。
+    pred_offsets: Vec<usize>,
+
+    /// 逆隣接リスト (CSR 形式) の本体。`pred_offsets[v]..pred_offsets[v + 1]` の範囲に
+    /// 頂点 `v` の predecessor (`succs[u] == v` なる `u`) が並ぶ。
+    pred_list: Vec<usize>,
+
+    /// `cycle_poss[v]`: 頂点 `v` がサイクル上にあるとき、そのサイクルの代表頂点からの
+    /// (辺を辿る向きの) 位置。代表頂点自身は 0。
+    cycle_poss: Vec<usize>,
+
+    /// 各サイクル頂点を根とする in-forest (非循環節部分を逆辺で辿った木) の
+    /// Euler tour における訪問時刻。`tin[v] <= tin[u] <= tout[v]` であることと
+    /// 「`v` が `u` の祖先である」ことは同値になる。
+    tin: Vec<usize>,
+
+    /// 上記 in-forest の Euler tour における離脱時刻。[`tin`] を参照。
+    tout: Vec<usize>,
+
     /// source である頂点たち。
     sources: Vec<usize>,
 
@@ -48,6 +73,14 @@ impl FunctionalGraph {
 
         let (sources, is_sources) = Self::init_sources(n, &succs);
 
+        let up = Self::init_up(n, &succs);
+
+        let (pred_offsets, pred_list) = Self::init_preds(n, &succs);
+
+        let cycle_poss = Self::init_cycle_poss(&succs, &cycle_reprs, &cycle_lens);
+
+        let (tin, tout) = Self::init_euler(n, &noncycle_lens, &pred_offsets, &pred_list);
+
         Self {
             n,
             succs,
@@ -55,6 +88,12 @@ impl FunctionalGraph {
             cycle_lens,
             cycle_ids,
             noncycle_lens,
+            up,
+            pred_offsets,
+            pred_list,
+            cycle_poss,
+            tin,
+            tout,
             sources,
             is_sources,
         }
@@ -147,6 +186,117 @@ impl FunctionalGraph {
         (cycle_reprs, cycle_lens, cycle_ids, noncycle_lens)
     }
 
+    /// ダブリング用テーブルを作る。`up[0]` は `succs` のコピーであり、
+    /// `up[j][v] = up[j-1][up[j-1][v]]` によって `up[j]` を順に埋めていく。
+    fn init_up(n: usize, succs: &[usize]) -> Vec<Vec<usize>> {
+        let levels = usize::BITS as usize;
+
+        let mut up = Vec::with_capacity(levels);
+        up.push(succs.to_vec());
+
+        for j in 1..levels {
+            let prev = &up[j - 1];
+            let cur: Vec<usize> = (0..n).map(|v| prev[prev[v]]).collect();
+            up.push(cur);
+        }
+
+        up
+    }
+
+    /// 逆隣接リスト (CSR 形式) を作る。
+    ///
+    /// まず各頂点の in-degree を数え、累積和をとって `pred_offsets` を得る。
+    /// その後、各辺を対応する範囲に書き込んでいく (scatter)。
+    fn init_preds(n: usize, succs: &[usize]) -> (Vec<usize>, Vec<usize>) {
+        let mut pred_offsets = vec![0usize; n + 1];
+        for &succ in succs {
+            pred_offsets[succ + 1] += 1;
+        }
+        for i in 0..n {
+            pred_offsets[i + 1] += pred_offsets[i];
+        }
+
+        let mut cursors = pred_offsets.clone();
+        let mut pred_list = vec![0usize; n];
+        for (v, &succ) in succs.iter().enumerate() {
+            pred_list[cursors[succ]] = v;
+            cursors[succ] += 1;
+        }
+
+        (pred_offsets, pred_list)
+    }
+
+    /// 各サイクル頂点の、そのサイクルの代表頂点からの位置を求める。
+    fn init_cycle_poss(
+        succs: &[usize],
+        cycle_reprs: &[usize],
+        cycle_lens: &[NonZeroUsize],
+    ) -> Vec<usize> {
+        let mut cycle_poss = vec![usize::MAX; succs.len()];
+
+        for (&repr, &len) in cycle_reprs.iter().zip(cycle_lens) {
+            let mut v = repr;
+            for pos in 0..len.get() {
+                cycle_poss[v] = pos;
+                v = succs[v];
+            }
+        }
+
+        cycle_poss
+    }
+
+    /// 各サイクル頂点を根とする in-forest (非循環節部分を逆辺で辿った木) の Euler tour を行い、
+    /// 各頂点の訪問時刻 (`tin`) と離脱時刻 (`tout`) を求める。
+    ///
+    /// サイクル頂点の predecessor の中には同じサイクル上の頂点 (逆辺で見ると前の周回頂点) が
+    /// 混ざっているが、非循環節長が 0 な頂点は in-forest の根にはなっても子にはならないので、
+    /// 子として辿る際に除外する。
+    fn init_euler(
+        n: usize,
+        noncycle_lens: &[usize],
+        pred_offsets: &[usize],
+        pred_list: &[usize],
+    ) -> (Vec<usize>, Vec<usize>) {
+        let mut tin = vec![0usize; n];
+        let mut tout = vec![0usize; n];
+        let mut timer = 0usize;
+
+        // (頂点, 次に調べる pred_list 中のインデックス) のスタック。
+        let mut stack = Vec::<(usize, usize)>::new();
+
+        for root in 0..n {
+            if noncycle_lens[root] != 0 {
+                continue;
+            }
+
+            tin[root] = timer;
+            timer += 1;
+            stack.push((root, pred_offsets[root]));
+
+            while let Some(&mut (v, ref mut cursor)) = stack.last_mut() {
+                if *cursor < pred_offsets[v + 1] {
+                    let c = pred_list[*cursor];
+                    *cursor += 1;
+
+                    // サイクル頂点は in-forest の子にはならない。
+                    if noncycle_lens[c] == 0 {
+                        continue;
+                    }
+
+                    tin[c] = timer;
+                    timer += 1;
+                    stack.push((c, pred_offsets[c]));
+                } else {
+                    tout[v] = timer;
+                    timer += 1;
+                    stack.pop();
+                }
+            }
+        }
+
+        (tin, tout)
+    }
+
     fn init_sources(n: usize, succs: &[usize]) -> (Vec<usize>, Vec<bool>) {
         let mut is_sources = vec![true; n];
 
@@ -170,10 +320,10 @@ impl FunctionalGraph {
     }
 
     /// 指定した頂点から k 回辺を辿ったときの頂点を返す。
+    ///
+    /// サイクル内を何度も周回するのは無駄なので、内部で適切に `k` の剰余をとった上で
+    /// ダブリングにより O(log k) で求める。
     pub fn kth_succ(&self, v: usize, k: usize) -> usize {
-        // TODO: ダブリングで対数時間にできる
-
-        // サイクル内を何度も周回するのは無駄なので、適切に剰余をとる。
         let k_opt = {
             let ncl = self.noncycle_len_of(v);
             if k >= ncl {
@@ -184,18 +334,167 @@ impl FunctionalGraph {
             }
         };
 
-        (0..k_opt).fold(v, |v, _| self.succs[v])
+        self.kth_succ_no_reduce(v, k_opt)
+    }
+
+    /// 指定した頂点から k 回辺を辿ったときの頂点を返す。
+    ///
+    /// `kth_succ` と異なり、サイクルによる `k` の剰余をとらず、ダブリングテーブルのみを用いて
+    /// 生の k 回目の到達点を O(log k) で求める。
+    pub fn kth_succ_no_reduce(&self, v: usize, k: usize) -> usize {
+        let mut v = v;
+        let mut k = k;
+
+        for up_j in &self.up {
+            if k == 0 {
+                break;
+            }
+            if k & 1 != 0 {
+                v = up_j[v];
+            }
+            k >>= 1;
+        }
+
+        v
     }
 
     /// 指定した頂点からサイクルを 1 周するまで辺を辿り続けたときの頂点列を生成する。
     ///
     /// 頂点列は先頭に `v` 自身を含む。また、サイクル上の各頂点はちょうど 1 回ずつ現れる。
     pub fn path_from(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
-        let count = self.noncycle_len_of(v) + self.cycle_len_of(v).get();
+        let count = self.reachable_count(v);
 
         std::iter::successors(Some(v), |&v| Some(self.succs[v])).take(count)
     }
 
+    /// 指定した頂点から到達可能な (相異なる) 頂点数を返す。`path_from(v)` が生成する
+    /// 頂点列の長さに等しい。
+    ///
+    /// 非循環節上の頂点 v については `1 + reachable_count(succ(v))` (サイクルに達するまでの
+    /// 距離にサイクル長を加えたもの) に等しく、サイクル上の頂点についてはそのサイクル長に
+    /// 等しい。これらは `new` の中で `noncycle_lens`・`cycle_lens` として逆拓撲順に一度だけ
+    /// 計算・記憶済みなので、ここでは単にその和を返すだけでよい。
+    pub fn reachable_count(&self, v: usize) -> usize {
+        self.noncycle_len_of(v) + self.cycle_len_of(v).get()
+    }
+
+    /// 順序対 `(u, v)` であって `u` から `v` へ辺を辿って到達できるものの総数を返す。
+    /// これは全頂点についての [`Self::reachable_count`] の総和に等しい。
+    pub fn reachable_pair_count(&self) -> u128 {
+        (0..self.n)
+            .map(|v| self.reachable_count(v) as u128)
+            .sum()
+    }
+
+    /// 頂点 `u` から辺を辿り続けて頂点 `v` に到達できるかどうかを返す。
+    pub fn can_reach(&self, u: usize, v: usize) -> bool {
+        self.steps_to(u, v).is_some()
+    }
+
+    /// 頂点 `u` から辺を辿り続けて頂点 `v` に到達するまでの辺数を返す。
+    /// 到達できない場合は `None` を返す。
+    ///
+    /// `v` がサイクル上にあるなら、`u` と同じサイクルに属してさえいれば必ず到達できる。
+    /// `v` が非循環節上にあるなら、`v` が `u` の祖先 (in-forest 上で `u` からサイクルへ
+    /// 向かう経路上にあるような頂点) であるときに限り到達できる。
+    pub fn steps_to(&self, u: usize, v: usize) -> Option<usize> {
+        if self.cycle_ids[u] != self.cycle_ids[v] {
+            return None;
+        }
+
+        if self.noncycle_len_of(v) == 0 {
+            // v はサイクル上にある。u からサイクルに入る頂点を経て v に至る。
+            let ncl_u = self.noncycle_len_of(u);
+            let entry = self.kth_succ_no_reduce(u, ncl_u);
+
+            let cl = self.cycle_len_of(v).get();
+            let pos_entry = self.cycle_poss[entry];
+            let pos_v = self.cycle_poss[v];
+            let offset = (pos_v + cl - pos_entry) % cl;
+
+            Some(ncl_u + offset)
+        } else {
+            // v は非循環節上にある。v が u の祖先であるときに限り到達できる。
+            let is_ancestor = self.tin[v] <= self.tin[u] && self.tin[u] <= self.tout[v];
+            is_ancestor.then(|| self.noncycle_len_of(u) - self.noncycle_len_of(v))
+        }
+    }
+
+    /// 頂点 `u`、`v` それぞれから辺を辿り続けたとき、経路が最初に合流する頂点
+    /// `meet_vertex` と、そこに至るまでの辺数 `(steps_u, steps_v)` を `(meet_vertex,
+    /// steps_u, steps_v)` の形で返す。`u` と `v` が別の成分に属する場合は `None` を返す。
+    ///
+    /// `u`、`v` がともに非循環節上にあり同じ in-forest の根 (サイクルへの進入点) を持つなら、
+    /// 合流点はその祖先森における LCA であり、ダブリングテーブルを使って求める。
+    /// そうでなければ合流点はサイクル上にあり、候補は `u`、`v` それぞれのサイクル進入点の
+    /// 2 つ。どちらを採用しても到達は可能だが、`max(steps_u, steps_v)` (遅い方が到達する
+    /// までの歩数) がより小さくなる候補を実際に選んで採用する。
+    pub fn confluence(&self, u: usize, v: usize) -> Option<(usize, usize, usize)> {
+        if self.cycle_ids[u] != self.cycle_ids[v] {
+            return None;
+        }
+
+        let ncl_u = self.noncycle_len_of(u);
+        let ncl_v = self.noncycle_len_of(v);
+
+        let (mut su, mut sv) = (u, v);
+        let (mut steps_u, mut steps_v) = (0, 0);
+
+        // まず深さ (非循環節上の残り長さ) を揃える。
+        if ncl_u > ncl_v {
+            steps_u = ncl_u - ncl_v;
+            su = self.kth_succ_no_reduce(u, steps_u);
+        } else if ncl_v > ncl_u {
+            steps_v = ncl_v - ncl_u;
+            sv = self.kth_succ_no_reduce(v, steps_v);
+        }
+
+        if su == sv {
+            return Some((su, steps_u, steps_v));
+        }
+
+        let entry_u = self.kth_succ_no_reduce(u, ncl_u);
+        let entry_v = self.kth_succ_no_reduce(v, ncl_v);
+
+        if entry_u == entry_v {
+            // 同じ根を持つ木の中で合流する: ダブリングで祖先方向へ辿り、LCA を求める。
+            for (j, up_j) in self.up.iter().enumerate().rev() {
+                if up_j[su] != up_j[sv] {
+                    su = up_j[su];
+                    sv = up_j[sv];
+                    steps_u += 1 << j;
+                    steps_v += 1 << j;
+                }
+            }
+
+            let meet = self.succ(su);
+
+            Some((meet, steps_u + 1, steps_v + 1))
+        } else {
+            // 木の中では合流しない: 合流点の候補は entry_u・entry_v の 2 つだが、
+            // 「進入点に早く達する方」をそのまま採用するのは誤り
+            // (もう一方がそこに至るまでに要する歩数を考慮していないため、
+            // 実際にはより小さい max(steps_u, steps_v) を与える候補を見落とすことがある)。
+            // そこで各候補についてもう一方からの到達歩数を実際に求め、
+            // max(steps_u, steps_v) がより小さい方を合流点として採用する。
+            let steps_v_to_entry_u = self
+                .steps_to(v, entry_u)
+                .expect("u, v は同じ成分に属するので entry_u に到達できるはず");
+            let steps_u_to_entry_v = self
+                .steps_to(u, entry_v)
+                .expect("u, v は同じ成分に属するので entry_v に到達できるはず");
+
+            let cost_entry_u = ncl_u.max(steps_v_to_entry_u);
+            let cost_entry_v = ncl_v.max(steps_u_to_entry_v);
+
+            if cost_entry_u < cost_entry_v {
+                Some((entry_u, ncl_u, steps_v_to_entry_u))
+            } else {
+                Some((entry_v, steps_u_to_entry_v, ncl_v))
+            }
+        }
+    }
+
     /// サイクルの個数を返す。これは弱連結成分の個数に等しい。
     pub fn cycle_count(&self) -> usize {
         self.cycle_reprs.len()
@@ -225,6 +524,20 @@ impl FunctionalGraph {
         self.noncycle_lens[v]
     }
 
+    /// 指定した頂点の predecessor (1 回辺を辿るとその頂点に達するような頂点) を列挙する。
+    /// 順序は未規定。
+    pub fn preds(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        let start = self.pred_offsets[v];
+        let end = self.pred_offsets[v + 1];
+
+        self.pred_list[start..end].iter().copied()
+    }
+
+    /// 指定した頂点の in-degree (predecessor の個数) を返す。
+    pub fn in_degree(&self, v: usize) -> usize {
+        self.pred_offsets[v + 1] - self.pred_offsets[v]
+    }
+
     /// source の個数を返す。
     pub fn source_count(&self) -> usize {
         self.sources.len()
@@ -241,6 +554,59 @@ impl FunctionalGraph {
     }
 }
 
+/// 遷移関数 `f` と開始点 `start` を指定して、単一の軌道の形状 `(非循環節長 μ, サイクル長 λ)`
+/// を Brent のサイクル検出法により求める。
+///
+/// `FunctionalGraph::new` は `0..n` 全体について `succs` を `O(n)` で保持するため、
+/// 32-bit 乱数生成器のように `n` が非常に大きい (2^32 など) 場合は現実的でない。
+/// この関数は `O(1)` の追加ストレージで、個々の seed の軌道だけを調べたい場合に使う。
+pub fn orbit_shape(mut f: impl FnMut(u64) -> u64, start: u64) -> (u64, u64) {
+    let mut power = 1u64;
+    let mut lam = 1u64;
+    let mut tortoise = start;
+    let mut hare = f(start);
+
+    while tortoise != hare {
+        if power == lam {
+            tortoise = hare;
+            power *= 2;
+            lam = 0;
+        }
+        hare = f(hare);
+        lam += 1;
+    }
+
+    let mut tortoise = start;
+    let mut hare = start;
+    for _ in 0..lam {
+        hare = f(hare);
+    }
+
+    let mut mu = 0u64;
+    while tortoise != hare {
+        tortoise = f(tortoise);
+        hare = f(hare);
+        mu += 1;
+    }
+
+    (mu, lam)
+}
+
+/// 遷移関数 `f` と開始点 `start` から、`orbit_shape` で求めた非循環節長 `mu` とサイクル長
+/// `lambda` を用いて、サイクルをちょうど 1 周するまでの頂点列を生成する。
+///
+/// [`FunctionalGraph::path_from`] の、グラフを構築しない版に相当する。
+pub fn orbit_path(
+    mut f: impl FnMut(u64) -> u64,
+    start: u64,
+    mu: u64,
+    lambda: u64,
+) -> impl Iterator<Item = u64> {
+    let count = usize::try_from(mu + lambda).expect("count should fit in usize");
+
+    std::iter::successors(Some(start), move |&v| Some(f(v))).take(count)
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::{assert_equal, Itertools as _};
@@ -269,6 +635,9 @@ mod tests {
         assert_eq!(fg.kth_succ(0, 0), 0);
         assert_eq!(fg.kth_succ(0, 5), 0);
 
+        assert_eq!(fg.kth_succ_no_reduce(0, 0), 0);
+        assert_eq!(fg.kth_succ_no_reduce(0, 5), 0);
+
         assert_equal(fg.path_from(0), [0]);
         assert_equal(fg.path_from(1), [1]);
         assert_equal(fg.path_from(2), [2]);
@@ -285,6 +654,28 @@ mod tests {
         assert_eq!(fg.noncycle_len_of(1), 0);
         assert_eq!(fg.noncycle_len_of(2), 0);
 
+        assert_equal(fg.preds(0), [0]);
+        assert_equal(fg.preds(1), [1]);
+        assert_equal(fg.preds(2), [2]);
+
+        assert_eq!(fg.in_degree(0), 1);
+        assert_eq!(fg.in_degree(1), 1);
+        assert_eq!(fg.in_degree(2), 1);
+
+        assert!(fg.can_reach(0, 0));
+        assert!(!fg.can_reach(0, 1));
+        assert!(!fg.can_reach(1, 0));
+
+        assert_eq!(fg.steps_to(0, 0), Some(0));
+        assert_eq!(fg.steps_to(0, 1), None);
+        assert_eq!(fg.steps_to(1, 0), None);
+
+        assert_eq!(fg.reachable_count(0), 1);
+        assert_eq!(fg.reachable_count(1), 1);
+        assert_eq!(fg.reachable_count(2), 1);
+
+        assert_eq!(fg.reachable_pair_count(), 3);
+
         assert_eq!(fg.source_count(), 0);
 
         assert_equal(fg.sources(), []);
@@ -308,6 +699,9 @@ mod tests {
         assert_eq!(fg.kth_succ(2, 5), 1);
         assert_eq!(fg.kth_succ(4, 3 * 1_000_000_000 + 2), 3);
 
+        assert_eq!(fg.kth_succ_no_reduce(0, 11), 2);
+        assert_eq!(fg.kth_succ_no_reduce(2, 5), 1);
+
         assert_equal(fg.path_from(0), [0, 1, 2, 3]);
         assert_equal(fg.path_from(2), [2, 3, 1]);
         assert_equal(fg.path_from(4), [4, 2, 3, 1]);
@@ -328,6 +722,57 @@ mod tests {
         assert_eq!(fg.noncycle_len_of(2), 0);
         assert_eq!(fg.noncycle_len_of(4), 1);
 
+        assert_equal(fg.preds(0), []);
+        assert_equal(fg.preds(1), [0, 3]);
+        assert_equal(fg.preds(2), [1, 4]);
+        assert_equal(fg.preds(3), [2]);
+        assert_equal(fg.preds(4), []);
+
+        assert_eq!(fg.in_degree(0), 0);
+        assert_eq!(fg.in_degree(1), 2);
+        assert_eq!(fg.in_degree(2), 2);
+        assert_eq!(fg.in_degree(3), 1);
+        assert_eq!(fg.in_degree(4), 0);
+
+        assert!(fg.can_reach(0, 0));
+        assert!(fg.can_reach(0, 1));
+        assert!(fg.can_reach(0, 2));
+        assert!(fg.can_reach(0, 3));
+        assert!(!fg.can_reach(0, 4));
+
+        assert!(fg.can_reach(4, 2));
+        assert!(fg.can_reach(4, 3));
+        assert!(fg.can_reach(4, 1));
+        assert!(!fg.can_reach(4, 0));
+
+        assert!(!fg.can_reach(2, 0));
+        assert!(!fg.can_reach(2, 4));
+        assert!(fg.can_reach(2, 3));
+
+        assert_eq!(fg.steps_to(0, 0), Some(0));
+        assert_eq!(fg.steps_to(0, 1), Some(1));
+        assert_eq!(fg.steps_to(0, 2), Some(2));
+        assert_eq!(fg.steps_to(0, 3), Some(3));
+        assert_eq!(fg.steps_to(0, 4), None);
+
+        assert_eq!(fg.steps_to(4, 2), Some(1));
+        assert_eq!(fg.steps_to(4, 3), Some(2));
+        assert_eq!(fg.steps_to(4, 1), Some(3));
+
+        assert_eq!(fg.steps_to(2, 0), None);
+        assert_eq!(fg.steps_to(2, 3), Some(1));
+
+        assert_eq!(fg.confluence(0, 0), Some((0, 0, 0)));
+        assert_eq!(fg.confluence(0, 4), Some((2, 2, 1)));
+        assert_eq!(fg.confluence(0, 2), Some((2, 2, 0)));
+        assert_eq!(fg.confluence(2, 4), Some((2, 0, 1)));
+
+        assert_eq!(fg.reachable_count(0), 4);
+        assert_eq!(fg.reachable_count(2), 3);
+        assert_eq!(fg.reachable_count(4), 4);
+
+        assert_eq!(fg.reachable_pair_count(), 17);
+
         assert_eq!(fg.source_count(), 2);
 
         assert_equal(fg.sources(), [0, 4]);
@@ -336,4 +781,54 @@ mod tests {
         assert!(!fg.is_source(2));
         assert!(fg.is_source(4));
     }
+
+    #[test]
+    fn test_confluence_tree() {
+        // 0 -> 2 ----> 3 <-> 4 (サイクル)
+        // 1 -> 2 ----^
+        let fg = graph_from_succs([2, 2, 3, 4, 3]);
+
+        assert_eq!(fg.confluence(0, 1), Some((2, 1, 1)));
+        assert_eq!(fg.confluence(0, 2), Some((2, 1, 0)));
+        assert_eq!(fg.confluence(0, 0), Some((0, 0, 0)));
+    }
+
+    #[test]
+    fn test_confluence_non_minimal_entry() {
+        // confluence の合流点候補 (entry_u, entry_v) は、単純に早く到達する方を採用すると
+        // より小さい max(steps_u, steps_v) を与える合流点を見落とすことがある回帰テスト。
+
+        // 0 -> 1 -> 4 -> 1 (サイクル long = {1, 4})
+        //      ^
+        // 2 ---+
+        // 3 -> 3 (自己ループ)
+        let fg = graph_from_succs([1, 4, 1, 3, 1]);
+
+        // succ(0) == 1, succ(4) == 1 なので、どちらも 1 歩で頂点 1 に合流する。
+        // entry_v (= 4) をそのまま採用すると (4, 2, 0) という、より遅い合流点を返してしまう。
+        assert_eq!(fg.confluence(0, 4), Some((1, 1, 1)));
+
+        let fg = graph_from_succs([3, 6, 3, 5, 5, 7, 0, 5, 9, 7]);
+
+        // 7 -> 5 は 1 歩、3 -> 5 -> 7 は 2 歩なので、頂点 5 が max(1, 1) = 1 でより早く合流する。
+        assert_eq!(fg.confluence(3, 7), Some((5, 1, 1)));
+    }
+
+    #[test]
+    fn test_orbit_shape() {
+        // 0 -> 1 ----> 2 <- 4
+        //      ^       |
+        //      |       |
+        //      +-- 3 <-+
+        let succs = [1u64, 2, 3, 1, 2];
+        let f = |v: u64| succs[usize::try_from(v).unwrap()];
+
+        assert_eq!(orbit_shape(f, 0), (1, 3));
+        assert_eq!(orbit_shape(f, 2), (0, 3));
+        assert_eq!(orbit_shape(f, 4), (1, 3));
+
+        assert_equal(orbit_path(f, 0, 1, 3), [0, 1, 2, 3]);
+        assert_equal(orbit_path(f, 2, 0, 3), [2, 3, 1]);
+        assert_equal(orbit_path(f, 4, 1, 3), [4, 2, 3, 1]);
+    }
 }